@@ -0,0 +1,135 @@
+//! Incremental maintenance of a fitted CF model's sparse matrix and
+//! neighbor table, so new interactions don't require a full refit.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::similarities::{pair_similarity, NeighborTable, SimMeasure};
+use crate::sparse::SparseMatrix;
+
+/// Merge `new_triplets` (row, col, value) into `mat`, returning the updated
+/// matrix along with the set of rows that received at least one new triplet
+/// (whether or not its value differed from the existing cell).
+///
+/// A new triplet whose `(row, col)` already has a cell in `mat` overwrites
+/// that cell's value rather than adding a second entry for it — streaming
+/// callers naturally re-send events for items a user has already interacted
+/// with, and a duplicate column entry would double-count that pair in
+/// `similarities::pair_similarity`'s sorted merge.
+pub fn merge_interactions(
+    mat: &SparseMatrix,
+    new_triplets: &[(i32, i32, f32)],
+) -> (SparseMatrix, HashSet<i32>) {
+    let mut touched = HashSet::new();
+    let mut cells: HashMap<(i32, i32), f32> = HashMap::with_capacity(mat.n_nnz() + new_triplets.len());
+    for row in 0..mat.n_rows {
+        let (idx, val) = mat.row(row);
+        for (&c, &v) in idx.iter().zip(val.iter()) {
+            cells.insert((row as i32, c), v);
+        }
+    }
+    let mut n_rows = mat.n_rows;
+    let mut n_cols = mat.n_cols;
+    for &(row, col, val) in new_triplets {
+        cells.insert((row, col), val);
+        touched.insert(row);
+        n_rows = n_rows.max(row as usize + 1);
+        n_cols = n_cols.max(col as usize + 1);
+    }
+    let triplets: Vec<(i32, i32, f32)> = cells.into_iter().map(|((row, col), val)| (row, col, val)).collect();
+    let merged = SparseMatrix::from_triplets(triplets, n_rows, n_cols);
+    (merged, touched)
+}
+
+/// Recompute neighbor lists for `touched` rows only, leaving every other
+/// row's entry in `table` untouched.
+///
+/// This is an approximation, not a fully consistent recompute: a row that
+/// isn't in `touched` keeps its previously computed neighbor list even if
+/// one of its *existing* neighbors is a touched row whose similarity to it
+/// has since changed. In other words, updates only ever flow into the rows
+/// that received new events, never back out to rows that referenced them.
+/// That staleness is bounded by how often `update()` is called relative to
+/// a full refit, and is the tradeoff this module makes in exchange for not
+/// recomputing the whole O(n^2) table on every incoming mini-batch.
+pub fn update_neighbors(
+    mat: &SparseMatrix,
+    table: &mut NeighborTable,
+    touched: &HashSet<i32>,
+    measure: SimMeasure,
+    k: usize,
+) {
+    if touched.is_empty() {
+        return;
+    }
+    for &row in touched {
+        let row_idx = row as usize;
+        if row_idx >= mat.n_rows || mat.row_len(row_idx) == 0 {
+            table.remove(&row);
+            continue;
+        }
+        let mut sims: Vec<(i32, f32)> = Vec::new();
+        for other in 0..mat.n_rows {
+            if other == row_idx || mat.row_len(other) == 0 {
+                continue;
+            }
+            let sim = pair_similarity(mat, row_idx, other, measure);
+            if sim > 0.0 {
+                sims.push((other as i32, sim));
+            }
+        }
+        sims.sort_unstable_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+        sims.truncate(k);
+        table.insert(row, sims);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merge_tracks_touched_rows_and_preserves_existing_data() {
+        let mat = SparseMatrix::from_triplets(vec![(0, 0, 1.0), (1, 1, 1.0)], 2, 2);
+        let (merged, touched) = merge_interactions(&mat, &[(0, 1, 2.0)]);
+        assert_eq!(touched, HashSet::from([0]));
+        assert_eq!(merged.row_len(0), 2);
+        assert_eq!(merged.row_len(1), 1);
+    }
+
+    #[test]
+    fn merge_overwrites_existing_cell_instead_of_duplicating_it() {
+        let mat = SparseMatrix::from_triplets(vec![(0, 0, 1.0), (1, 0, 1.0)], 2, 2);
+        let (merged, touched) = merge_interactions(&mat, &[(0, 0, 5.0)]);
+        assert_eq!(touched, HashSet::from([0]));
+        // Same row count, not doubled: the repeated (row 0, col 0) cell was
+        // overwritten, not appended as a second entry.
+        assert_eq!(merged.row_len(0), 1);
+        assert_eq!(merged.row(0), (&[0][..], &[5.0][..]));
+    }
+
+    #[test]
+    fn redundant_update_does_not_corrupt_similarity_of_identical_rows() {
+        let mat = SparseMatrix::from_triplets(vec![(0, 0, 1.0), (1, 0, 1.0)], 2, 2);
+        assert_eq!(pair_similarity(&mat, 0, 1, SimMeasure::Cosine), 1.0);
+        // Row 0 re-reports the exact same interaction it already has.
+        let (merged, _) = merge_interactions(&mat, &[(0, 0, 1.0)]);
+        assert_eq!(pair_similarity(&merged, 0, 1, SimMeasure::Cosine), 1.0);
+    }
+
+    #[test]
+    fn update_neighbors_only_recomputes_touched_rows() {
+        let mat = SparseMatrix::from_triplets(
+            vec![(0, 0, 1.0), (1, 0, 1.0), (2, 1, 1.0)],
+            3,
+            2,
+        );
+        let mut table: NeighborTable = HashMap::new();
+        table.insert(1, vec![(0, 0.5)]);
+        table.insert(2, vec![(99, 1.0)]); // stale placeholder, not in `touched`
+        let touched = HashSet::from([0]);
+        update_neighbors(&mat, &mut table, &touched, SimMeasure::Cosine, 5);
+        assert_eq!(table[&0], vec![(1, 1.0)]);
+        // Untouched row 2 keeps its previous (now stale) entry untouched.
+        assert_eq!(table[&2], vec![(99, 1.0)]);
+    }
+}
@@ -1,4 +1,8 @@
 #![allow(clippy::too_many_arguments)]
+// pyo3's `#[pymethods]` expansion trips this lint on our pinned pyo3 version;
+// the generated `impl` blocks are sound, just not "local" by the lint's
+// definition.
+#![allow(non_local_definitions)]
 
 use pyo3::prelude::*;
 
@@ -18,6 +22,9 @@ fn recfarm(_py: Python, m: &PyModule) -> PyResult<()> {
     m.add_class::<user_cf::PyUserCF>()?;
     m.add_function(wrap_pyfunction!(user_cf::save, m)?)?;
     m.add_function(wrap_pyfunction!(user_cf::load, m)?)?;
+    m.add_class::<item_cf::PyItemCF>()?;
+    m.add_function(wrap_pyfunction!(item_cf::save, m)?)?;
+    m.add_function(wrap_pyfunction!(item_cf::load, m)?)?;
     m.add_function(wrap_pyfunction!(utils::build_consumed, m)?)?;
     m.add("__version__", VERSION)?;
     Ok(())
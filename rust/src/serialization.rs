@@ -0,0 +1,106 @@
+//! Binary (de)serialization helpers for persisting fitted CF models.
+//!
+//! Payloads are plain bincode by default. When `compression_level` is set,
+//! `save_to_file` additionally frames the bincode bytes as a zstd stream
+//! prefixed with the `RFZ1` magic header; `load_from_file` sniffs that
+//! header so both old uncompressed files and new zstd-framed ones load
+//! transparently.
+
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Write};
+
+use pyo3::exceptions::PyIOError;
+use pyo3::prelude::*;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// Magic header prefixing zstd-compressed payloads.
+const ZSTD_MAGIC: &[u8; 4] = b"RFZ1";
+
+/// Write `value` to `path` as bincode-encoded bytes, optionally zstd
+/// compressed when `compression_level` is `Some`.
+pub fn save_to_file<T: Serialize>(
+    value: &T,
+    path: &str,
+    compression_level: Option<i32>,
+) -> PyResult<()> {
+    let bytes = bincode::serialize(value).map_err(|e| PyIOError::new_err(e.to_string()))?;
+    let file = File::create(path).map_err(|e| PyIOError::new_err(e.to_string()))?;
+    let mut writer = BufWriter::new(file);
+    match compression_level {
+        Some(level) => {
+            writer
+                .write_all(ZSTD_MAGIC)
+                .map_err(|e| PyIOError::new_err(e.to_string()))?;
+            let compressed = zstd::stream::encode_all(bytes.as_slice(), level)
+                .map_err(|e| PyIOError::new_err(e.to_string()))?;
+            writer
+                .write_all(&compressed)
+                .map_err(|e| PyIOError::new_err(e.to_string()))
+        }
+        None => writer
+            .write_all(&bytes)
+            .map_err(|e| PyIOError::new_err(e.to_string())),
+    }
+}
+
+/// Read a value previously written by [`save_to_file`], transparently
+/// decompressing zstd-framed payloads and falling back to plain bincode
+/// for legacy, uncompressed files.
+pub fn load_from_file<T: DeserializeOwned>(path: &str) -> PyResult<T> {
+    let file = File::open(path).map_err(|e| PyIOError::new_err(e.to_string()))?;
+    let mut reader = BufReader::new(file);
+    let mut raw = Vec::new();
+    reader
+        .read_to_end(&mut raw)
+        .map_err(|e| PyIOError::new_err(e.to_string()))?;
+
+    let bytes: Vec<u8> = if raw.starts_with(ZSTD_MAGIC) {
+        zstd::stream::decode_all(&raw[ZSTD_MAGIC.len()..])
+            .map_err(|e| PyIOError::new_err(e.to_string()))?
+    } else {
+        raw
+    };
+
+    bincode::deserialize(&bytes).map_err(|e| PyIOError::new_err(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tmp_path(name: &str) -> String {
+        use std::sync::atomic::{AtomicU32, Ordering};
+        static COUNTER: AtomicU32 = AtomicU32::new(0);
+        let n = COUNTER.fetch_add(1, Ordering::Relaxed);
+        std::env::temp_dir()
+            .join(format!(
+                "recfarm_serialization_test_{name}_{}_{n}",
+                std::process::id()
+            ))
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    #[test]
+    fn round_trips_uncompressed() {
+        let path = tmp_path("uncompressed");
+        let value: Vec<i32> = vec![1, 2, 3, 4, 5];
+        save_to_file(&value, &path, None).unwrap();
+        let loaded: Vec<i32> = load_from_file(&path).unwrap();
+        assert_eq!(value, loaded);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn round_trips_zstd_compressed() {
+        let path = tmp_path("compressed");
+        let value: Vec<i32> = (0..1000).collect();
+        save_to_file(&value, &path, Some(3)).unwrap();
+        let raw = std::fs::read(&path).unwrap();
+        assert!(raw.starts_with(ZSTD_MAGIC));
+        let loaded: Vec<i32> = load_from_file(&path).unwrap();
+        assert_eq!(value, loaded);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,150 @@
+//! Approximate neighbor-candidate generation via MinHash + banded LSH.
+//!
+//! Exact all-pairs similarity (see the parent module) is O(n^2) over rows,
+//! which dominates fit time on large sparse matrices. This module trades a
+//! little recall for a large speedup: each row is summarized by a MinHash
+//! signature, rows are bucketed by bands of that signature, and only rows
+//! that collide in at least one band are treated as similarity candidates.
+//! Exact similarity is then evaluated solely on those candidate pairs.
+
+use std::collections::HashMap;
+
+use crate::sparse::SparseMatrix;
+
+/// A large prime used as the modulus for the MinHash linear hash family.
+const MERSENNE_PRIME: u64 = (1u64 << 61) - 1;
+
+/// Deterministic MinHash signature generator: `k` independent hash
+/// functions `h_i(x) = (a_i * x + b_i) mod p`.
+pub struct MinHasher {
+    a: Vec<u64>,
+    b: Vec<u64>,
+}
+
+impl MinHasher {
+    /// Build a hasher with `num_hashes` functions, deterministically seeded
+    /// so the same `seed` always yields the same signatures.
+    pub fn new(num_hashes: usize, seed: u64) -> Self {
+        let mut state = seed.wrapping_mul(0x9E3779B97F4A7C15).wrapping_add(1);
+        let mut next = || {
+            // xorshift64*, good enough for picking hash coefficients.
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state.wrapping_mul(0x2545F4914F6CDD1D) % MERSENNE_PRIME
+        };
+        let a = (0..num_hashes).map(|_| next().max(1)).collect();
+        let b = (0..num_hashes).map(|_| next()).collect();
+        Self { a, b }
+    }
+
+    /// MinHash signature of a row, represented as its sorted column ids.
+    pub fn signature(&self, ids: &[i32]) -> Vec<u64> {
+        self.a
+            .iter()
+            .zip(self.b.iter())
+            .map(|(&ai, &bi)| {
+                ids.iter()
+                    .map(|&x| {
+                        let x = x as u64 as u128;
+                        ((ai as u128 * x + bi as u128) % MERSENNE_PRIME as u128) as u64
+                    })
+                    .min()
+                    .unwrap_or(0)
+            })
+            .collect()
+    }
+}
+
+/// Banded LSH index over a set of MinHash signatures.
+///
+/// `num_hashes = bands * rows_per_band`. Two rows become candidates if any
+/// of their `bands` band-tuples hash to the same bucket; `bands` and
+/// `rows_per_band` should be tuned so the collision threshold
+/// `(1/bands)^(1/rows_per_band)` approximates the desired similarity cutoff.
+pub struct Lsh {
+    bands: usize,
+    rows_per_band: usize,
+}
+
+impl Lsh {
+    pub fn new(bands: usize, rows_per_band: usize) -> Self {
+        Self {
+            bands,
+            rows_per_band,
+        }
+    }
+
+    /// Emit candidate row-index pairs that collide in at least one band.
+    pub fn candidate_pairs(&self, signatures: &[Vec<u64>]) -> Vec<(usize, usize)> {
+        let mut buckets: HashMap<(usize, Vec<u64>), Vec<usize>> = HashMap::new();
+        for (row, sig) in signatures.iter().enumerate() {
+            for band in 0..self.bands {
+                let start = band * self.rows_per_band;
+                let end = (start + self.rows_per_band).min(sig.len());
+                if start >= end {
+                    continue;
+                }
+                buckets
+                    .entry((band, sig[start..end].to_vec()))
+                    .or_default()
+                    .push(row);
+            }
+        }
+        let mut pairs = std::collections::HashSet::new();
+        for rows in buckets.values() {
+            for i in 0..rows.len() {
+                for j in (i + 1)..rows.len() {
+                    let (a, b) = (rows[i], rows[j]);
+                    pairs.insert(if a < b { (a, b) } else { (b, a) });
+                }
+            }
+        }
+        pairs.into_iter().collect()
+    }
+}
+
+/// Build MinHash signatures for every row of `mat`, indexed by row; an
+/// empty row gets an all-zero signature.
+pub fn signatures_for_matrix(mat: &SparseMatrix, hasher: &MinHasher) -> Vec<Vec<u64>> {
+    (0..mat.n_rows)
+        .map(|row| {
+            let (ids, _) = mat.row(row);
+            hasher.signature(ids)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signature_is_deterministic_for_same_seed() {
+        let hasher_a = MinHasher::new(32, 42);
+        let hasher_b = MinHasher::new(32, 42);
+        let ids = [1, 5, 9, 42];
+        assert_eq!(hasher_a.signature(&ids), hasher_b.signature(&ids));
+    }
+
+    #[test]
+    fn identical_rows_always_collide() {
+        let hasher = MinHasher::new(16, 7);
+        let ids = [3, 8, 15, 21];
+        let sig = hasher.signature(&ids);
+        let signatures = vec![sig.clone(), sig];
+        let lsh = Lsh::new(4, 4);
+        let pairs = lsh.candidate_pairs(&signatures);
+        assert_eq!(pairs, vec![(0, 1)]);
+    }
+
+    #[test]
+    fn disjoint_rows_rarely_collide() {
+        let hasher = MinHasher::new(16, 7);
+        let a = hasher.signature(&[1, 2, 3, 4]);
+        let b = hasher.signature(&[1000, 2000, 3000, 4000]);
+        let lsh = Lsh::new(4, 4);
+        let pairs = lsh.candidate_pairs(&[a, b]);
+        assert!(pairs.is_empty());
+    }
+}
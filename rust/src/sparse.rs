@@ -0,0 +1,62 @@
+//! Minimal CSR-style sparse matrix used to hold user-item interaction data.
+
+use serde::{Deserialize, Serialize};
+
+/// A row-major sparse matrix in compressed-sparse-row form.
+///
+/// Rows are users (or items, depending on which axis a caller treats as
+/// primary); columns are the complementary axis. `indptr` has `n_rows + 1`
+/// entries, `indices`/`data` are parallel arrays of column id / value.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SparseMatrix {
+    pub indptr: Vec<usize>,
+    pub indices: Vec<i32>,
+    pub data: Vec<f32>,
+    pub n_rows: usize,
+    pub n_cols: usize,
+}
+
+impl SparseMatrix {
+    /// Build a CSR matrix from unsorted `(row, col, value)` triplets.
+    pub fn from_triplets(
+        mut triplets: Vec<(i32, i32, f32)>,
+        n_rows: usize,
+        n_cols: usize,
+    ) -> Self {
+        triplets.sort_unstable_by_key(|&(row, col, _)| (row, col));
+        let mut indptr = vec![0usize; n_rows + 1];
+        let mut indices = Vec::with_capacity(triplets.len());
+        let mut data = Vec::with_capacity(triplets.len());
+        for (row, col, val) in triplets {
+            indptr[row as usize + 1] += 1;
+            indices.push(col);
+            data.push(val);
+        }
+        for i in 0..n_rows {
+            indptr[i + 1] += indptr[i];
+        }
+        Self {
+            indptr,
+            indices,
+            data,
+            n_rows,
+            n_cols,
+        }
+    }
+
+    #[inline]
+    pub fn row(&self, row: usize) -> (&[i32], &[f32]) {
+        let start = self.indptr[row];
+        let end = self.indptr[row + 1];
+        (&self.indices[start..end], &self.data[start..end])
+    }
+
+    #[inline]
+    pub fn row_len(&self, row: usize) -> usize {
+        self.indptr[row + 1] - self.indptr[row]
+    }
+
+    pub fn n_nnz(&self) -> usize {
+        self.data.len()
+    }
+}
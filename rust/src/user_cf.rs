@@ -0,0 +1,330 @@
+//! User-based collaborative filtering: find users similar to a target user
+//! and recommend items those neighbors consumed.
+
+use std::collections::HashMap;
+
+use numpy::{IntoPyArray, PyArray1};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::incremental::{merge_interactions, update_neighbors};
+use crate::serialization::{load_from_file, save_to_file};
+use crate::similarities::{compute_neighbors, compute_neighbors_lsh, NeighborTable, SimMeasure};
+use crate::sparse::SparseMatrix;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserCF {
+    pub matrix: SparseMatrix,
+    pub neighbors: NeighborTable,
+    pub consumed: HashMap<i32, Vec<(i32, f32)>>,
+    pub k_sim: usize,
+}
+
+impl UserCF {
+    fn predict_one(&self, user: i32, item: i32) -> f32 {
+        let Some(neighbors) = self.neighbors.get(&user) else {
+            return 0.0;
+        };
+        let mut weighted_sum = 0.0f32;
+        let mut sim_sum = 0.0f32;
+        for &(other_user, sim) in neighbors {
+            if let Some(events) = self.consumed.get(&other_user) {
+                if let Some(&(_, label)) = events.iter().find(|(i, _)| *i == item) {
+                    weighted_sum += sim * label;
+                    sim_sum += sim.abs();
+                }
+            }
+        }
+        if sim_sum == 0.0 {
+            0.0
+        } else {
+            weighted_sum / sim_sum
+        }
+    }
+
+    fn recommend_one(&self, user: i32, n_rec: usize) -> Vec<(i32, f32)> {
+        let Some(neighbors) = self.neighbors.get(&user) else {
+            return Vec::new();
+        };
+        let already_seen: std::collections::HashSet<i32> = self
+            .consumed
+            .get(&user)
+            .map(|events| events.iter().map(|(i, _)| *i).collect())
+            .unwrap_or_default();
+
+        let mut scores: HashMap<i32, (f32, f32)> = HashMap::new();
+        for &(other_user, sim) in neighbors {
+            let Some(events) = self.consumed.get(&other_user) else {
+                continue;
+            };
+            for &(item, label) in events {
+                if already_seen.contains(&item) {
+                    continue;
+                }
+                let entry = scores.entry(item).or_insert((0.0, 0.0));
+                entry.0 += sim * label;
+                entry.1 += sim.abs();
+            }
+        }
+        let mut ranked: Vec<(i32, f32)> = scores
+            .into_iter()
+            .map(|(item, (weighted, sim_sum))| {
+                let score = if sim_sum == 0.0 { 0.0 } else { weighted / sim_sum };
+                (item, score)
+            })
+            .collect();
+        ranked.sort_unstable_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+        ranked.truncate(n_rec);
+        ranked
+    }
+}
+
+/// Python-visible user-based CF model.
+#[pyclass]
+pub struct PyUserCF {
+    model: Option<UserCF>,
+    measure: SimMeasure,
+}
+
+#[pymethods]
+impl PyUserCF {
+    #[new]
+    fn new(measure: &str) -> PyResult<Self> {
+        Ok(Self {
+            model: None,
+            measure: parse_measure(measure)?,
+        })
+    }
+
+    /// Fit the model from flat `(user, item, label)` interaction arrays.
+    ///
+    /// `use_lsh` selects `similarities::compute_neighbors_lsh` over the
+    /// exact scan; see that function's doc for the tradeoff.
+    #[pyo3(signature = (users, items, labels, n_users, n_items, k_sim, use_lsh=false, num_hashes=64, bands=16))]
+    fn fit(
+        &mut self,
+        users: Vec<i32>,
+        items: Vec<i32>,
+        labels: Vec<f32>,
+        n_users: usize,
+        n_items: usize,
+        k_sim: usize,
+        use_lsh: bool,
+        num_hashes: usize,
+        bands: usize,
+    ) -> PyResult<()> {
+        let triplets: Vec<(i32, i32, f32)> = users
+            .iter()
+            .zip(items.iter())
+            .zip(labels.iter())
+            .map(|((&u, &i), &l)| (u, i, l))
+            .collect();
+        let matrix = SparseMatrix::from_triplets(triplets, n_users, n_items);
+        let neighbors = if use_lsh {
+            compute_neighbors_lsh(&matrix, self.measure, k_sim, num_hashes, bands)
+        } else {
+            compute_neighbors(&matrix, self.measure, k_sim)
+        };
+        let mut consumed: HashMap<i32, Vec<(i32, f32)>> = HashMap::new();
+        for ((&u, &i), &l) in users.iter().zip(items.iter()).zip(labels.iter()) {
+            consumed.entry(u).or_default().push((i, l));
+        }
+        self.model = Some(UserCF {
+            matrix,
+            neighbors,
+            consumed,
+            k_sim,
+        });
+        Ok(())
+    }
+
+    fn predict(&self, user: i32, item: i32) -> PyResult<f32> {
+        let model = self.fitted()?;
+        Ok(model.predict_one(user, item))
+    }
+
+    fn recommend_user(&self, user: i32, n_rec: usize) -> PyResult<Vec<(i32, f32)>> {
+        let model = self.fitted()?;
+        Ok(model.recommend_one(user, n_rec))
+    }
+
+    /// Return the top-k neighbors previously computed for `user`.
+    fn get_neighbors(&self, user: i32) -> PyResult<Vec<(i32, f32)>> {
+        let model = self.fitted()?;
+        Ok(model.neighbors.get(&user).cloned().unwrap_or_default())
+    }
+
+    /// Fold newly observed `(user, item, label)` events into the fitted
+    /// model in place: merges them into the sparse matrix and `consumed`
+    /// map, then recomputes neighbor lists only for the touched users via
+    /// `incremental::update_neighbors`, instead of a full refit. See that
+    /// function's doc for the staleness tradeoff this makes.
+    fn update(&mut self, users: Vec<i32>, items: Vec<i32>, labels: Vec<f32>) -> PyResult<()> {
+        let measure = self.measure;
+        let model = self.fitted_mut()?;
+        let new_triplets: Vec<(i32, i32, f32)> = users
+            .iter()
+            .zip(items.iter())
+            .zip(labels.iter())
+            .map(|((&u, &i), &l)| (u, i, l))
+            .collect();
+        let (matrix, touched) = merge_interactions(&model.matrix, &new_triplets);
+        model.matrix = matrix;
+        for ((&u, &i), &l) in users.iter().zip(items.iter()).zip(labels.iter()) {
+            let events = model.consumed.entry(u).or_default();
+            match events.iter_mut().find(|(item, _)| *item == i) {
+                Some(existing) => existing.1 = l,
+                None => events.push((i, l)),
+            }
+        }
+        update_neighbors(&model.matrix, &mut model.neighbors, &touched, measure, model.k_sim);
+        Ok(())
+    }
+
+    /// Export the fitted top-k neighbor graph as a `(source, neighbor,
+    /// weight)` edge list of numpy arrays, for external inspection or
+    /// visualization with any graph/embedding viewer.
+    fn to_edgelist<'py>(
+        &self,
+        py: Python<'py>,
+    ) -> PyResult<(&'py PyArray1<i32>, &'py PyArray1<i32>, &'py PyArray1<f32>)> {
+        let model = self.fitted()?;
+        let mut src = Vec::new();
+        let mut dst = Vec::new();
+        let mut weight = Vec::new();
+        for (&user, neighbors) in &model.neighbors {
+            for &(neighbor, sim) in neighbors {
+                src.push(user);
+                dst.push(neighbor);
+                weight.push(sim);
+            }
+        }
+        Ok((
+            src.into_pyarray(py),
+            dst.into_pyarray(py),
+            weight.into_pyarray(py),
+        ))
+    }
+
+    /// Node-level metadata accompanying `to_edgelist`: each user id paired
+    /// with the number of items it has interacted with, useful for sizing
+    /// nodes or spotting degenerate hubs in a viewer.
+    fn node_metadata<'py>(
+        &self,
+        py: Python<'py>,
+    ) -> PyResult<(&'py PyArray1<i32>, &'py PyArray1<i32>)> {
+        let model = self.fitted()?;
+        let mut ids = Vec::with_capacity(model.consumed.len());
+        let mut degrees = Vec::with_capacity(model.consumed.len());
+        for (&user, events) in &model.consumed {
+            ids.push(user);
+            degrees.push(events.len() as i32);
+        }
+        Ok((ids.into_pyarray(py), degrees.into_pyarray(py)))
+    }
+}
+
+impl PyUserCF {
+    fn fitted(&self) -> PyResult<&UserCF> {
+        self.model
+            .as_ref()
+            .ok_or_else(|| PyValueError::new_err("PyUserCF has not been fit yet"))
+    }
+
+    fn fitted_mut(&mut self) -> PyResult<&mut UserCF> {
+        self.model
+            .as_mut()
+            .ok_or_else(|| PyValueError::new_err("PyUserCF has not been fit yet"))
+    }
+}
+
+pub(crate) fn parse_measure(measure: &str) -> PyResult<SimMeasure> {
+    match measure {
+        "cosine" => Ok(SimMeasure::Cosine),
+        "jaccard" => Ok(SimMeasure::Jaccard),
+        "pearson" => Ok(SimMeasure::Pearson),
+        other => Err(PyValueError::new_err(format!(
+            "unknown similarity measure: {other}"
+        ))),
+    }
+}
+
+#[pyfunction]
+#[pyo3(signature = (model, path, compression_level=None))]
+pub fn save(model: &PyUserCF, path: &str, compression_level: Option<i32>) -> PyResult<()> {
+    let fitted = model.fitted()?;
+    save_to_file(fitted, path, compression_level)
+}
+
+#[pyfunction]
+pub fn load(path: &str, measure: &str) -> PyResult<PyUserCF> {
+    let model: UserCF = load_from_file(path)?;
+    Ok(PyUserCF {
+        model: Some(model),
+        measure: parse_measure(measure)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture() -> UserCF {
+        // User 0's two neighbors (sim 1.0 and 0.5) both have item 10; only
+        // neighbor 2 has also consumed item 20.
+        let mut neighbors: NeighborTable = HashMap::new();
+        neighbors.insert(0, vec![(1, 1.0), (2, 0.5)]);
+        let mut consumed: HashMap<i32, Vec<(i32, f32)>> = HashMap::new();
+        consumed.insert(1, vec![(10, 5.0)]);
+        consumed.insert(2, vec![(10, 1.0), (20, 4.0)]);
+        UserCF {
+            matrix: SparseMatrix::from_triplets(Vec::new(), 3, 30),
+            neighbors,
+            consumed,
+            k_sim: 5,
+        }
+    }
+
+    #[test]
+    fn predict_one_blends_neighbor_labels_weighted_by_similarity() {
+        let model = fixture();
+        // weighted = 1.0*5.0 + 0.5*1.0 = 5.5, sim_sum = 1.0 + 0.5 = 1.5
+        assert_eq!(model.predict_one(0, 10), 5.5 / 1.5);
+    }
+
+    #[test]
+    fn predict_one_is_zero_when_no_neighbor_consumed_the_item() {
+        let model = fixture();
+        assert_eq!(model.predict_one(0, 99), 0.0);
+    }
+
+    #[test]
+    fn predict_one_is_zero_for_a_user_with_no_neighbors() {
+        let model = fixture();
+        assert_eq!(model.predict_one(42, 10), 0.0);
+    }
+
+    #[test]
+    fn recommend_one_ranks_by_similarity_weighted_score() {
+        let model = fixture();
+        // item 20: 0.5*4.0 / 0.5 = 4.0; item 10: 5.5 / 1.5 = 3.667 — 20 wins.
+        let recs = model.recommend_one(0, 5);
+        assert_eq!(recs, vec![(20, 4.0), (10, 5.5 / 1.5)]);
+    }
+
+    #[test]
+    fn recommend_one_excludes_already_seen_items() {
+        let mut model = fixture();
+        // User 0 has already seen item 10, so only item 20 should surface.
+        model.consumed.insert(0, vec![(10, 1.0)]);
+        let recs = model.recommend_one(0, 5);
+        assert_eq!(recs, vec![(20, 4.0)]);
+    }
+
+    #[test]
+    fn recommend_one_is_empty_for_a_user_with_no_neighbors() {
+        let model = fixture();
+        assert_eq!(model.recommend_one(42, 5), Vec::new());
+    }
+}
@@ -0,0 +1,23 @@
+//! Small helpers shared across the CF models.
+
+use std::collections::HashMap;
+
+use pyo3::prelude::*;
+
+/// Build a `user -> [(item, label)]` lookup from flat interaction arrays.
+///
+/// This is the shape both `user_cf` and `item_cf` consume when fitting: for
+/// every user we need its full list of interacted items (and the associated
+/// label/rating) without re-scanning the raw arrays on every lookup.
+#[pyfunction]
+pub fn build_consumed(
+    users: Vec<i32>,
+    items: Vec<i32>,
+    labels: Vec<f32>,
+) -> PyResult<HashMap<i32, Vec<(i32, f32)>>> {
+    let mut consumed: HashMap<i32, Vec<(i32, f32)>> = HashMap::new();
+    for ((&u, &i), &l) in users.iter().zip(items.iter()).zip(labels.iter()) {
+        consumed.entry(u).or_default().push((i, l));
+    }
+    Ok(consumed)
+}